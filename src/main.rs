@@ -1,8 +1,34 @@
-use std::collections::BTreeSet;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, BinaryHeap, HashSet, VecDeque};
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Read};
 use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
+/// Simulation tick length in seconds; physics integrates once per tick.
+const TICK_DT: f64 = 0.1;
+/// Position/velocity tolerance for considering a car "arrived" at a stop.
+const ARRIVAL_EPSILON: f64 = 0.01;
+const MAX_ACCELERATION: f64 = 1.0;
+const MAX_VELOCITY: f64 = 2.5;
+/// How long a car holds its doors open at a stop, in seconds.
+const STOP_DWELL_SECONDS: f64 = 2.0;
+/// How many ticks `BeamScheduler` simulates forward when scoring a rollout.
+const LOOKAHEAD_HORIZON_TICKS: usize = 15;
+/// Default number of partial plans `BeamScheduler` keeps after each expansion.
+const DEFAULT_BEAM_WIDTH: usize = 50;
+/// `dispatch_optimal` wait-time penalty, in milliseconds, for assigning a
+/// call to an elevator already moving the requested direction but past the
+/// floor: it has to finish its current sweep, then reverse twice to come
+/// back for the call, the worst case a SCAN-style dispatcher can hand it.
+const SAME_DIRECTION_BEHIND_PENALTY_MILLIS: u64 = 10_000;
+/// `dispatch_optimal` wait-time penalty, in milliseconds, for assigning a
+/// call to an elevator moving the opposite direction from the one
+/// requested: it only has to finish its current sweep and reverse once.
+const OPPOSITE_DIRECTION_PENALTY_MILLIS: u64 = 5_000;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Direction {
     Up,
@@ -16,9 +42,46 @@ enum Command {
     Status,
 }
 
+/// Describes a building's vertical geometry: the height of each floor above
+/// the one below it, so floors need not all be the same distance apart.
+struct Building {
+    floor_heights: Vec<f64>,
+}
+
+impl Building {
+    fn new(floor_heights: Vec<f64>) -> Self {
+        Self { floor_heights }
+    }
+
+    /// Vertical distance in meters from the ground (floor 0) up to `floor`.
+    fn cumulative_height(&self, floor: i32) -> f64 {
+        let floor = floor.clamp(0, self.floor_heights.len() as i32) as usize;
+        self.floor_heights.iter().take(floor).sum()
+    }
+
+    /// The floor whose height most closely matches `location`.
+    fn floor_at(&self, location: f64) -> i32 {
+        let mut closest_floor = 0;
+        let mut closest_distance = f64::MAX;
+
+        for floor in 0..=self.floor_heights.len() as i32 {
+            let distance = (self.cumulative_height(floor) - location).abs();
+            if distance < closest_distance {
+                closest_distance = distance;
+                closest_floor = floor;
+            }
+        }
+
+        closest_floor
+    }
+}
+
 struct ElevatorState {
     id: usize,
-    current_floor: i32,
+    location: f64,
+    velocity: f64,
+    acceleration: f64,
+    motor_input: f64,
     direction: Direction,
     stops: BTreeSet<i32>,
 }
@@ -27,35 +90,283 @@ impl ElevatorState {
     fn new(id: usize) -> Self {
         Self {
             id,
-            current_floor: 0,
+            location: 0.0,
+            velocity: 0.0,
+            acceleration: 0.0,
+            motor_input: 0.0,
             direction: Direction::Idle,
             stops: BTreeSet::new(),
         }
     }
 
-    fn calculate_score(&self, floor: i32, direction: Direction) -> i32 {
-        let distance = (self.current_floor - floor).abs();
-        match self.direction {
-            Direction::Idle => distance,
-            Direction::Up => {
-                if direction == Direction::Up && floor >= self.current_floor {
-                    distance
-                } else if direction == Direction::Up {
-                    distance + 1000
-                } else {
-                    distance + 500
-                }
+    /// The floor the car currently occupies, resolved from its continuous location.
+    fn current_floor(&self, building: &Building) -> i32 {
+        building.floor_at(self.location)
+    }
+}
+
+/// A lightweight, `Mutex`-free snapshot of a car's physics state, used to run
+/// hypothetical rollouts (e.g. in `BeamScheduler`) without touching the real
+/// `ElevatorState`.
+#[derive(Clone)]
+struct SimCar {
+    location: f64,
+    velocity: f64,
+    direction: Direction,
+    stops: BTreeSet<i32>,
+}
+
+/// Computes the acceleration a car should apply this tick to reach a target
+/// location and come to rest on it, without exceeding kinematic limits.
+trait MotionController {
+    fn next_acceleration(&self, velocity: f64, remaining: f64) -> f64;
+}
+
+/// Accelerates toward `max_velocity`, then brakes just in time to stop
+/// exactly on target using the kinematic bound `d <= v^2 / (2 * a_max)`,
+/// padded by one tick of travel (`|v| * TICK_DT`).
+struct SmoothMotionController {
+    max_acceleration: f64,
+    max_velocity: f64,
+}
+
+impl SmoothMotionController {
+    fn new(max_acceleration: f64, max_velocity: f64) -> Self {
+        Self {
+            max_acceleration,
+            max_velocity,
+        }
+    }
+}
+
+impl MotionController for SmoothMotionController {
+    fn next_acceleration(&self, velocity: f64, remaining: f64) -> f64 {
+        let sign = if remaining >= 0.0 { 1.0 } else { -1.0 };
+        let distance = remaining.abs();
+        // The braking check only runs once per tick, so without a margin
+        // the car keeps moving at roughly its current speed for one more
+        // `TICK_DT` before braking can kick in, systematically overshooting
+        // the target by about `velocity * TICK_DT`.
+        let braking_distance =
+            (velocity * velocity) / (2.0 * self.max_acceleration) + velocity.abs() * TICK_DT;
+
+        let accel = if distance <= braking_distance {
+            // Oppose the car's actual direction of travel, not the sign of
+            // `remaining`: right after it overshoots the target by a hair
+            // (unavoidable with discrete ticks), `remaining` flips sign
+            // before `velocity` does, and braking against `remaining`
+            // would shove the car further past the target instead of
+            // killing its speed.
+            let travel_sign = if velocity >= 0.0 { 1.0 } else { -1.0 };
+            -travel_sign * self.max_acceleration
+        } else if sign * velocity < self.max_velocity {
+            sign * self.max_acceleration
+        } else {
+            0.0
+        };
+
+        accel.clamp(-self.max_acceleration, self.max_acceleration)
+    }
+}
+
+/// Time to travel `distance` meters from a standing stop to a standing stop,
+/// accelerating up to `max_velocity` when there's room for it and braking to
+/// rest at the end, per the same kinematic bound `SmoothMotionController` flies by.
+fn travel_time(distance: f64, max_acceleration: f64, max_velocity: f64) -> f64 {
+    if distance <= 0.0 {
+        return 0.0;
+    }
+
+    let accel_distance = max_velocity * max_velocity / (2.0 * max_acceleration);
+
+    if distance >= 2.0 * accel_distance {
+        let accel_time = max_velocity / max_acceleration;
+        let cruise_distance = distance - 2.0 * accel_distance;
+        let cruise_time = cruise_distance / max_velocity;
+        2.0 * accel_time + cruise_time
+    } else {
+        2.0 * (distance / max_acceleration).sqrt()
+    }
+}
+
+/// A partial assignment of pending hall calls to elevators, scored by the
+/// total passenger wait time accrued so far. Ordering is reversed so that
+/// `BinaryHeap`, a max-heap, pops the lowest-cost node first.
+struct DispatchNode {
+    cost_millis: u64,
+    assignment: Vec<Option<usize>>,
+    position: Vec<f64>,
+    elapsed_millis: Vec<u64>,
+    /// Each elevator's direction of travel once it has serviced every call
+    /// committed to it so far in this branch, starting from its actual
+    /// current direction. Used to price in a SCAN-style penalty for handing
+    /// a call to a car that is moving away from it.
+    direction: Vec<Direction>,
+}
+
+impl PartialEq for DispatchNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost_millis == other.cost_millis
+    }
+}
+
+impl Eq for DispatchNode {}
+
+impl PartialOrd for DispatchNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DispatchNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost_millis.cmp(&self.cost_millis)
+    }
+}
+
+/// A candidate assignment explored by `BeamScheduler`, carrying the simulated
+/// car states it would produce and the rollout cost scored for it so far.
+#[derive(Clone)]
+struct BeamPlan {
+    assignment: Vec<Option<usize>>,
+    cars: Vec<SimCar>,
+    cost: f64,
+}
+
+/// Anticipatory look-ahead scheduler: rather than scoring an assignment by a
+/// closed-form travel time like `dispatch_optimal` does, it simulates each
+/// candidate forward `horizon_ticks` of physics and scores it by total
+/// accrued passenger wait/travel time. Unlike a full search, the frontier is
+/// pruned to the best `beam_width` partial plans after each pending call is
+/// branched, trading optimality for the ability to plan several calls deep.
+struct BeamScheduler {
+    beam_width: usize,
+    horizon_ticks: usize,
+}
+
+impl BeamScheduler {
+    fn new(beam_width: usize, horizon_ticks: usize) -> Self {
+        Self {
+            beam_width,
+            horizon_ticks,
+        }
+    }
+
+    /// Runs `plan`'s cars forward `horizon_ticks` ticks and sums the
+    /// passenger-seconds left outstanding (pending stops across all cars,
+    /// each tick) as a proxy for total wait/travel time.
+    fn rollout_cost(
+        &self,
+        plan: &BeamPlan,
+        motion: &SmoothMotionController,
+        building: &Building,
+    ) -> f64 {
+        let mut cars = plan.cars.clone();
+        let mut cost = 0.0;
+
+        for _ in 0..self.horizon_ticks {
+            for car in &mut cars {
+                simulate_tick(car, building, motion);
             }
-            Direction::Down => {
-                if direction == Direction::Down && floor <= self.current_floor {
-                    distance
-                } else if direction == Direction::Down {
-                    distance + 1000
-                } else {
-                    distance + 500
+            let pending: usize = cars.iter().map(|car| car.stops.len()).sum();
+            cost += pending as f64 * TICK_DT;
+        }
+
+        cost
+    }
+
+    /// Beam-searches assignments of `pending` calls to `cars` and returns the
+    /// elevator that should take the first pending call right now.
+    fn plan(
+        &self,
+        cars: &[SimCar],
+        building: &Building,
+        pending: &[(i32, Direction)],
+    ) -> Option<usize> {
+        if pending.is_empty() {
+            return None;
+        }
+
+        let motion = SmoothMotionController::new(MAX_ACCELERATION, MAX_VELOCITY);
+
+        let mut beam = vec![BeamPlan {
+            assignment: vec![None; pending.len()],
+            cars: cars.to_vec(),
+            cost: 0.0,
+        }];
+
+        // `_direction` is intentionally unused: see the rationale on
+        // `request_elevator_lookahead`, the only caller that threads a
+        // `Direction` in here.
+        for (call_index, &(floor, _direction)) in pending.iter().enumerate() {
+            let mut candidates = Vec::new();
+
+            for plan in &beam {
+                for elevator_index in 0..cars.len() {
+                    let mut cars = plan.cars.clone();
+                    cars[elevator_index].stops.insert(floor);
+
+                    let mut assignment = plan.assignment.clone();
+                    assignment[call_index] = Some(elevator_index);
+
+                    let mut candidate = BeamPlan {
+                        assignment,
+                        cars,
+                        cost: 0.0,
+                    };
+                    candidate.cost = self.rollout_cost(&candidate, &motion, building);
+                    candidates.push(candidate);
                 }
             }
+
+            candidates.sort_by(|a, b| a.cost.partial_cmp(&b.cost).unwrap());
+            candidates.truncate(self.beam_width.max(1));
+            beam = candidates;
         }
+
+        beam.into_iter()
+            .min_by(|a, b| a.cost.partial_cmp(&b.cost).unwrap())
+            .and_then(|plan| plan.assignment[0])
+    }
+}
+
+impl Default for BeamScheduler {
+    fn default() -> Self {
+        Self::new(DEFAULT_BEAM_WIDTH, LOOKAHEAD_HORIZON_TICKS)
+    }
+}
+
+/// Hall calls that have arrived but not yet been assigned to a car, in the
+/// order they were placed.
+struct RequestQueue {
+    calls: VecDeque<(i32, Direction)>,
+}
+
+impl RequestQueue {
+    fn new() -> Self {
+        Self {
+            calls: VecDeque::new(),
+        }
+    }
+
+    fn push(&mut self, floor: i32, direction: Direction) {
+        self.calls.push_back((floor, direction));
+    }
+
+    fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Removes and returns the oldest outstanding call, once it has been
+    /// committed to an elevator.
+    fn pop(&mut self) -> Option<(i32, Direction)> {
+        self.calls.pop_front()
+    }
+
+    /// A snapshot of the pending calls, in arrival order, for the dispatch
+    /// and scheduling algorithms that take `&[(i32, Direction)]`.
+    fn as_vec(&self) -> Vec<(i32, Direction)> {
+        self.calls.iter().copied().collect()
     }
 }
 
@@ -66,31 +377,179 @@ struct ElevatorHandle {
 
 struct Controller {
     elevators: Vec<ElevatorHandle>,
+    building: Arc<Building>,
+    beam_scheduler: BeamScheduler,
 }
 
 impl Controller {
-    fn new(elevators: Vec<ElevatorHandle>) -> Self {
-        Self { elevators }
+    fn new(elevators: Vec<ElevatorHandle>, building: Arc<Building>) -> Self {
+        Self {
+            elevators,
+            building,
+            beam_scheduler: BeamScheduler::default(),
+        }
     }
 
-    fn request_elevator(&self, floor: i32, direction: Direction) {
-        let mut best_score = i32::MAX;
-        let mut best_elevator = None;
+    /// Assigns every pending hall call to an elevator by Dijkstra search over
+    /// partial assignments: a node is which calls are assigned to which
+    /// elevator so far, an edge assigns one more call, and its cost is the
+    /// wait time that call's passenger would see given that elevator's stops
+    /// already committed in this branch, plus a SCAN-style penalty
+    /// (`SAME_DIRECTION_BEHIND_PENALTY_MILLIS`/`OPPOSITE_DIRECTION_PENALTY_MILLIS`)
+    /// when the call's requested direction disagrees with the way that
+    /// elevator is headed. Popping the lowest-cost complete assignment off
+    /// the `BinaryHeap` gives the one minimizing total summed wait time, the
+    /// same state-space shortest-path shape as the amphipod room-sorting
+    /// puzzle.
+    fn dispatch_optimal(&self, calls: &[(i32, Direction)]) -> Vec<(i32, usize)> {
+        let elevator_count = self.elevators.len();
+
+        let start_position: Vec<f64> = self
+            .elevators
+            .iter()
+            .map(|elevator| elevator.state.lock().unwrap().location)
+            .collect();
+
+        let start_direction: Vec<Direction> = self
+            .elevators
+            .iter()
+            .map(|elevator| elevator.state.lock().unwrap().direction)
+            .collect();
+
+        let start = DispatchNode {
+            cost_millis: 0,
+            assignment: vec![None; calls.len()],
+            position: start_position,
+            elapsed_millis: vec![0; elevator_count],
+            direction: start_direction,
+        };
+
+        let mut visited = HashSet::new();
+        visited.insert(start.assignment.clone());
+
+        let mut heap = BinaryHeap::new();
+        heap.push(start);
+
+        let mapping = loop {
+            let Some(node) = heap.pop() else {
+                break Vec::new();
+            };
 
-        for elevator in &self.elevators {
-            let state = elevator.state.lock().unwrap();
-            let score = state.calculate_score(floor, direction);
+            let Some(call_index) = node.assignment.iter().position(Option::is_none) else {
+                break calls
+                    .iter()
+                    .zip(node.assignment.iter())
+                    .map(|(&(floor, _), elevator)| (floor, elevator.unwrap()))
+                    .collect();
+            };
 
-            if score < best_score {
-                best_score = score;
-                best_elevator = Some(elevator);
+            let (floor, direction) = calls[call_index];
+            let target = self.building.cumulative_height(floor);
+
+            for elevator_index in 0..elevator_count {
+                let distance = (target - node.position[elevator_index]).abs();
+                let leg_millis =
+                    (travel_time(distance, MAX_ACCELERATION, MAX_VELOCITY) * 1000.0) as u64;
+
+                let direction_penalty_millis = match node.direction[elevator_index] {
+                    Direction::Idle => 0,
+                    Direction::Up => {
+                        if direction == Direction::Up && target >= node.position[elevator_index] {
+                            0
+                        } else if direction == Direction::Up {
+                            SAME_DIRECTION_BEHIND_PENALTY_MILLIS
+                        } else {
+                            OPPOSITE_DIRECTION_PENALTY_MILLIS
+                        }
+                    }
+                    Direction::Down => {
+                        if direction == Direction::Down && target <= node.position[elevator_index] {
+                            0
+                        } else if direction == Direction::Down {
+                            SAME_DIRECTION_BEHIND_PENALTY_MILLIS
+                        } else {
+                            OPPOSITE_DIRECTION_PENALTY_MILLIS
+                        }
+                    }
+                };
+                let wait_millis =
+                    node.elapsed_millis[elevator_index] + leg_millis + direction_penalty_millis;
+
+                let mut assignment = node.assignment.clone();
+                assignment[call_index] = Some(elevator_index);
+
+                if !visited.insert(assignment.clone()) {
+                    continue;
+                }
+
+                let mut position = node.position.clone();
+                position[elevator_index] = target;
+
+                let mut elapsed_millis = node.elapsed_millis.clone();
+                elapsed_millis[elevator_index] = wait_millis + (STOP_DWELL_SECONDS * 1000.0) as u64;
+
+                let mut direction_state = node.direction.clone();
+                direction_state[elevator_index] = direction;
+
+                heap.push(DispatchNode {
+                    cost_millis: node.cost_millis + wait_millis,
+                    assignment,
+                    position,
+                    elapsed_millis,
+                    direction: direction_state,
+                });
             }
+        };
+
+        for &(floor, elevator_index) in &mapping {
+            let elevator = &self.elevators[elevator_index];
+            elevator.sender.send(Command::AddStop(floor)).unwrap();
+            println!(
+                "Dispatch optimizer assigned floor {} to elevator {}",
+                floor,
+                elevator.state.lock().unwrap().id
+            );
         }
 
-        if let Some(elevator) = best_elevator {
+        mapping
+    }
+
+    /// Assigns the first call in `pending` using `BeamScheduler`'s rollout
+    /// search over the whole pending queue, then commits only that one
+    /// action via the existing `Command::AddStop`. Call again as new hall
+    /// calls arrive; each call replans over the current queue, so bunching a
+    /// few floors ahead gets accounted for without pinning down a full plan.
+    ///
+    /// Unlike `dispatch_optimal`, each call's requested `Direction` is carried
+    /// through but never scored here: `BeamScheduler` prices a candidate by
+    /// simulating its actual physics forward, so a car sent the "wrong way"
+    /// already pays for that in rollout cost via the extra ticks its stops
+    /// stay pending. A SCAN-style direction penalty on top would double-count
+    /// a cost the rollout already models directly.
+    fn request_elevator_lookahead(&self, pending: &[(i32, Direction)]) {
+        let Some(&(floor, _direction)) = pending.first() else {
+            return;
+        };
+
+        let cars: Vec<SimCar> = self
+            .elevators
+            .iter()
+            .map(|elevator| {
+                let state = elevator.state.lock().unwrap();
+                SimCar {
+                    location: state.location,
+                    velocity: state.velocity,
+                    direction: state.direction,
+                    stops: state.stops.clone(),
+                }
+            })
+            .collect();
+
+        if let Some(elevator_index) = self.beam_scheduler.plan(&cars, &self.building, pending) {
+            let elevator = &self.elevators[elevator_index];
             elevator.sender.send(Command::AddStop(floor)).unwrap();
             println!(
-                "Assigned floor {} to elevator {}",
+                "Beam scheduler assigned floor {} to elevator {}",
                 floor,
                 elevator.state.lock().unwrap().id
             );
@@ -98,7 +557,115 @@ impl Controller {
     }
 }
 
-fn elevator_loop(id: usize, receiver: mpsc::Receiver<Command>, state: Arc<Mutex<ElevatorState>>) {
+/// Picks the next stop to travel toward from the current floor, direction,
+/// and remaining stop set, flipping direction when no stop remains ahead in
+/// the current direction. Pure so it can drive both the real `elevator_loop` and simulated
+/// rollouts in `BeamScheduler`.
+fn next_target_floor_from(
+    current: i32,
+    direction: Direction,
+    stops: &BTreeSet<i32>,
+) -> (Direction, i32) {
+    if stops.contains(&current) {
+        return (direction, current);
+    }
+
+    let mut direction = direction;
+    let target;
+
+    match direction {
+        Direction::Up => {
+            if let Some(&floor) = stops.range(current + 1..).next() {
+                target = floor;
+            } else {
+                direction = Direction::Down;
+                target = *stops.range(..current).next_back().unwrap();
+            }
+        }
+        Direction::Down => {
+            if let Some(&floor) = stops.range(..current).next_back() {
+                target = floor;
+            } else {
+                direction = Direction::Up;
+                target = *stops.range(current + 1..).next().unwrap();
+            }
+        }
+        Direction::Idle => {
+            let up_stop = stops.range(current + 1..).next().copied();
+            let down_stop = stops.range(..current).next_back().copied();
+
+            match (up_stop, down_stop) {
+                (Some(u), Some(d)) => {
+                    if u - current <= current - d {
+                        direction = Direction::Up;
+                        target = u;
+                    } else {
+                        direction = Direction::Down;
+                        target = d;
+                    }
+                }
+                (Some(u), None) => {
+                    direction = Direction::Up;
+                    target = u;
+                }
+                (None, Some(d)) => {
+                    direction = Direction::Down;
+                    target = d;
+                }
+                (None, None) => unreachable!(),
+            }
+        }
+    }
+
+    (direction, target)
+}
+
+/// Picks the next stop to travel toward for a live `ElevatorState`, updating
+/// its direction in place.
+fn next_target_floor(state: &mut ElevatorState, building: &Building) -> i32 {
+    let current = state.current_floor(building);
+    let (direction, target) = next_target_floor_from(current, state.direction, &state.stops);
+    state.direction = direction;
+    target
+}
+
+/// Advances a simulated car by one `TICK_DT` tick, using the same routing and
+/// motion-control logic `elevator_loop` runs for real, so rollouts in
+/// `BeamScheduler` behave like the actual system.
+fn simulate_tick(car: &mut SimCar, building: &Building, motion: &SmoothMotionController) {
+    if car.stops.is_empty() {
+        car.direction = Direction::Idle;
+        return;
+    }
+
+    let current = building.floor_at(car.location);
+    let (direction, target_floor) = next_target_floor_from(current, car.direction, &car.stops);
+    car.direction = direction;
+
+    let target_location = building.cumulative_height(target_floor);
+    let remaining = target_location - car.location;
+
+    let accel = motion.next_acceleration(car.velocity, remaining);
+    car.velocity = (car.velocity + accel * TICK_DT).clamp(-MAX_VELOCITY, MAX_VELOCITY);
+    car.location += car.velocity * TICK_DT;
+
+    if (car.location - target_location).abs() < ARRIVAL_EPSILON
+        && car.velocity.abs() < ARRIVAL_EPSILON
+    {
+        car.location = target_location;
+        car.velocity = 0.0;
+        car.stops.remove(&target_floor);
+    }
+}
+
+fn elevator_loop(
+    id: usize,
+    receiver: mpsc::Receiver<Command>,
+    state: Arc<Mutex<ElevatorState>>,
+    building: Arc<Building>,
+) {
+    let motion = SmoothMotionController::new(MAX_ACCELERATION, MAX_VELOCITY);
+
     loop {
         while let Ok(cmd) = receiver.try_recv() {
             match cmd {
@@ -110,117 +677,624 @@ fn elevator_loop(id: usize, receiver: mpsc::Receiver<Command>, state: Arc<Mutex<
                 Command::Status => {
                     let state = state.lock().unwrap();
                     println!(
-                        "Elevator {}: Floor {}, Direction {:?}, Stops: {:?}",
-                        id, state.current_floor, state.direction, state.stops
+                        "Elevator {}: Floor {}, Location {:.2}m, Velocity {:.2}m/s, Direction {:?}, Stops: {:?}",
+                        id,
+                        state.current_floor(&building),
+                        state.location,
+                        state.velocity,
+                        state.direction,
+                        state.stops
                     );
                 }
             }
         }
 
-        let (next_floor, direction, should_stop) = {
+        let target_floor = {
             let mut state = state.lock().unwrap();
             if state.stops.is_empty() {
                 state.direction = Direction::Idle;
-                (state.current_floor, Direction::Idle, false)
+                None
             } else {
-                let current_floor = state.current_floor;
-                let mut direction = state.direction;
-                let mut next_floor = current_floor;
+                Some(next_target_floor(&mut state, &building))
+            }
+        };
 
-                match direction {
-                    Direction::Up => {
-                        if let Some(&next) = state.stops.range(current_floor + 1..).next() {
-                            next_floor = current_floor + 1;
-                        } else {
-                            direction = Direction::Down;
-                            if let Some(&next) = state.stops.range(..=current_floor).next_back() {
-                                next_floor = current_floor - 1;
-                            }
-                        }
-                    }
-                    Direction::Down => {
-                        if let Some(&next) = state.stops.range(..current_floor).next_back() {
-                            next_floor = current_floor - 1;
-                        } else {
-                            direction = Direction::Up;
-                            if let Some(&next) = state.stops.range(current_floor..).next() {
-                                next_floor = current_floor + 1;
-                            }
-                        }
-                    }
-                    Direction::Idle => {
-                        let up_stop = state.stops.range(current_floor + 1..).next();
-                        let down_stop = state.stops.range(..current_floor).next_back();
-
-                        match (up_stop, down_stop) {
-                            (Some(&u), Some(&d)) => {
-                                if u - current_floor <= current_floor - d {
-                                    direction = Direction::Up;
-                                    next_floor = current_floor + 1;
-                                } else {
-                                    direction = Direction::Down;
-                                    next_floor = current_floor - 1;
-                                }
-                            }
-                            (Some(&u), None) => {
-                                direction = Direction::Up;
-                                next_floor = current_floor + 1;
-                            }
-                            (None, Some(&d)) => {
-                                direction = Direction::Down;
-                                next_floor = current_floor - 1;
-                            }
-                            (None, None) => unreachable!(),
-                        }
-                    }
-                }
+        let Some(target_floor) = target_floor else {
+            thread::sleep(Duration::from_secs_f64(TICK_DT));
+            continue;
+        };
 
-                state.direction = direction;
-                let should_stop = state.stops.contains(&next_floor);
-                (next_floor, direction, should_stop)
-            }
+        let target_location = building.cumulative_height(target_floor);
+
+        let arrived = {
+            let mut state = state.lock().unwrap();
+            let remaining = target_location - state.location;
+
+            let accel = motion.next_acceleration(state.velocity, remaining);
+            state.motor_input = accel;
+            state.acceleration = accel;
+            state.velocity =
+                (state.velocity + state.acceleration * TICK_DT).clamp(-MAX_VELOCITY, MAX_VELOCITY);
+            state.location += state.velocity * TICK_DT;
+
+            (state.location - target_location).abs() < ARRIVAL_EPSILON
+                && state.velocity.abs() < ARRIVAL_EPSILON
         };
 
-        {
+        if arrived {
             let mut state = state.lock().unwrap();
-            state.current_floor = next_floor;
+            state.location = target_location;
+            state.velocity = 0.0;
+            state.acceleration = 0.0;
+            state.motor_input = 0.0;
+            state.stops.remove(&target_floor);
+            println!("Elevator {} stopped at floor {}", id, target_floor);
+            drop(state);
+            thread::sleep(Duration::from_secs(2));
+        } else {
+            thread::sleep(Duration::from_secs_f64(TICK_DT));
+        }
+    }
+}
 
-            if should_stop {
-                state.stops.remove(&next_floor);
-                println!("Elevator {} stopped at floor {}", id, next_floor);
-                drop(state);
-                thread::sleep(Duration::from_secs(2));
-            } else {
-                println!("Elevator {} passing floor {}", id, next_floor);
-                drop(state);
-                thread::sleep(Duration::from_secs(1));
+fn parse_direction(token: &str) -> Result<Direction, String> {
+    match token {
+        "up" => Ok(Direction::Up),
+        "down" => Ok(Direction::Down),
+        other => Err(format!(
+            "invalid direction `{other}` (expected `up` or `down`)"
+        )),
+    }
+}
+
+/// Parses a scenario description into a `Building` and the hall calls to
+/// seed on startup. Expected format (whitespace-separated tokens; blank
+/// lines and lines starting with `#` are ignored):
+///
+///   floors <count>
+///   heights <h0> <h1> ... <h(count-1)>
+///   request <floor> <up|down>
+///   ...one `request` line per hall call...
+fn parse_scenario(input: &str) -> Result<(Building, Vec<(i32, Direction)>), String> {
+    let mut floors_count = None;
+    let mut floor_heights = None;
+    let mut requests = Vec::new();
+
+    for (line_number, line) in input.lines().enumerate() {
+        let line_number = line_number + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut tokens = line.split_whitespace();
+        let keyword = tokens.next().unwrap();
+
+        match keyword {
+            "floors" => {
+                let count = tokens
+                    .next()
+                    .ok_or_else(|| format!("line {line_number}: `floors` needs a count"))?
+                    .parse::<usize>()
+                    .map_err(|_| format!("line {line_number}: invalid floor count"))?;
+                floors_count = Some(count);
+            }
+            "heights" => {
+                let heights = tokens
+                    .map(|token| {
+                        token
+                            .parse::<f64>()
+                            .map_err(|_| format!("line {line_number}: invalid height `{token}`"))
+                    })
+                    .collect::<Result<Vec<f64>, String>>()?;
+                floor_heights = Some(heights);
             }
+            "request" => {
+                let floor = tokens
+                    .next()
+                    .ok_or_else(|| format!("line {line_number}: `request` needs a floor"))?
+                    .parse::<i32>()
+                    .map_err(|_| format!("line {line_number}: invalid floor"))?;
+                let direction = tokens
+                    .next()
+                    .ok_or_else(|| format!("line {line_number}: `request` needs a direction"))
+                    .and_then(parse_direction)
+                    .map_err(|err| format!("line {line_number}: {err}"))?;
+                requests.push((floor, direction));
+            }
+            other => return Err(format!("line {line_number}: unknown keyword `{other}`")),
+        }
+    }
+
+    let floor_heights =
+        floor_heights.ok_or_else(|| "scenario is missing a `heights` line".to_string())?;
+
+    if let Some(count) = floors_count {
+        if count != floor_heights.len() {
+            return Err(format!(
+                "`floors {count}` does not match {} heights",
+                floor_heights.len()
+            ));
         }
     }
+
+    Ok((Building::new(floor_heights), requests))
+}
+
+fn read_scenario_input(path: &str) -> String {
+    if path == "-" {
+        let mut buffer = String::new();
+        io::stdin()
+            .read_to_string(&mut buffer)
+            .expect("failed to read scenario from stdin");
+        buffer
+    } else {
+        fs::read_to_string(path).unwrap_or_else(|err| panic!("failed to read {path}: {err}"))
+    }
+}
+
+fn default_scenario() -> (Building, Vec<(i32, Direction)>) {
+    // Ground-floor lobby is taller than the typical office floor above it.
+    let building = Building::new(vec![4.5, 3.0, 3.0, 3.0, 3.0, 3.0, 3.0, 3.0, 3.0]);
+    let requests = vec![(5, Direction::Up), (3, Direction::Down), (8, Direction::Up)];
+    (building, requests)
+}
+
+/// Reads `call <floor> <up|down>`, `goto <floor>`, `dispatch`, and `status`
+/// lines from `reader` until EOF, translating each into a `Controller`/
+/// `Command` action. `call` models a hall button (direction-aware hall
+/// call); `goto` models a car-operating-panel button (a passenger already
+/// aboard just wants a floor, so it carries no direction preference).
+/// Neither assigns anything by itself — they only enqueue onto `queue`, so
+/// a burst of calls entered back-to-back accumulates there. `dispatch` (and
+/// EOF, so nothing is left stranded) then drains the whole accumulated
+/// queue through `request_elevator_lookahead`, which plans every call still
+/// outstanding together instead of scoring each one against the cars alone.
+fn run_interactive<R: BufRead>(controller: &Controller, queue: &mut RequestQueue, reader: R) {
+    for line in reader.lines() {
+        let Ok(line) = line else { break };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("call") => match (tokens.next(), tokens.next()) {
+                (Some(floor), Some(direction)) => {
+                    match (floor.parse::<i32>(), parse_direction(direction)) {
+                        (Ok(floor), Ok(direction)) => queue.push(floor, direction),
+                        _ => println!("usage: call <floor> <up|down>"),
+                    }
+                }
+                _ => println!("usage: call <floor> <up|down>"),
+            },
+            Some("goto") => match tokens.next().map(str::parse::<i32>) {
+                Some(Ok(floor)) => queue.push(floor, Direction::Idle),
+                _ => println!("usage: goto <floor>"),
+            },
+            Some("dispatch") => dispatch_queue(controller, queue),
+            Some("status") => {
+                for elevator in &controller.elevators {
+                    elevator.sender.send(Command::Status).unwrap();
+                }
+            }
+            Some(other) => println!("unknown command `{other}`"),
+            None => {}
+        }
+    }
+
+    dispatch_queue(controller, queue);
+}
+
+/// Drains `queue` by repeatedly planning with `request_elevator_lookahead`
+/// over whatever remains and popping only the call it just committed, so
+/// each pass replans against the calls still outstanding rather than
+/// against a single one at a time.
+fn dispatch_queue(controller: &Controller, queue: &mut RequestQueue) {
+    while !queue.is_empty() {
+        controller.request_elevator_lookahead(&queue.as_vec());
+        queue.pop();
+    }
 }
 
 fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let (building, seeded_requests) = match args.get(1) {
+        Some(path) => {
+            let input = read_scenario_input(path);
+            parse_scenario(&input).unwrap_or_else(|err| panic!("invalid scenario: {err}"))
+        }
+        None => default_scenario(),
+    };
+
+    let building = Arc::new(building);
     let mut elevators = Vec::new();
 
     for id in 0..3 {
         let (sender, receiver) = mpsc::channel();
         let state = Arc::new(Mutex::new(ElevatorState::new(id)));
         let state_clone = Arc::clone(&state);
+        let building_clone = Arc::clone(&building);
 
         thread::spawn(move || {
-            elevator_loop(id, receiver, state_clone);
+            elevator_loop(id, receiver, state_clone, building_clone);
         });
 
         elevators.push(ElevatorHandle { sender, state });
     }
 
-    let controller = Controller::new(elevators);
+    let controller = Controller::new(elevators, Arc::clone(&building));
 
-    controller.request_elevator(5, Direction::Up);
-    controller.request_elevator(3, Direction::Down);
-    controller.request_elevator(8, Direction::Up);
+    let mut queue = RequestQueue::new();
+    for (floor, direction) in seeded_requests {
+        queue.push(floor, direction);
+    }
 
-    loop {
-        thread::sleep(Duration::from_secs(2));
+    if !queue.is_empty() {
+        controller.dispatch_optimal(&queue.as_vec());
+        while queue.pop().is_some() {}
+    }
+
+    run_interactive(&controller, &mut queue, io::stdin().lock());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accelerates_toward_a_distant_target() {
+        let motion = SmoothMotionController::new(MAX_ACCELERATION, MAX_VELOCITY);
+        let accel = motion.next_acceleration(0.0, 100.0);
+        assert_eq!(accel, MAX_ACCELERATION);
+    }
+
+    #[test]
+    fn brakes_when_within_stopping_distance() {
+        let motion = SmoothMotionController::new(MAX_ACCELERATION, MAX_VELOCITY);
+        // At v = 2.0 m/s the braking distance is v^2 / (2 * a) + v * TICK_DT = 2.2 m.
+        let accel = motion.next_acceleration(2.0, 1.5);
+        assert_eq!(accel, -MAX_ACCELERATION);
+    }
+
+    #[test]
+    fn coasts_once_cruising_at_max_velocity() {
+        let motion = SmoothMotionController::new(MAX_ACCELERATION, MAX_VELOCITY);
+        let accel = motion.next_acceleration(MAX_VELOCITY, 100.0);
+        assert_eq!(accel, 0.0);
+    }
+
+    #[test]
+    fn cumulative_height_sums_floor_heights_below_a_floor() {
+        let building = Building::new(vec![3.0, 4.0, 5.0]);
+        assert_eq!(building.cumulative_height(0), 0.0);
+        assert_eq!(building.cumulative_height(1), 3.0);
+        assert_eq!(building.cumulative_height(2), 7.0);
+        assert_eq!(building.cumulative_height(3), 12.0);
+    }
+
+    #[test]
+    fn cumulative_height_clamps_out_of_range_floors() {
+        let building = Building::new(vec![3.0, 4.0, 5.0]);
+        assert_eq!(building.cumulative_height(-1), 0.0);
+        assert_eq!(building.cumulative_height(10), 12.0);
+    }
+
+    #[test]
+    fn floor_at_resolves_to_the_nearest_floor() {
+        let building = Building::new(vec![3.0, 4.0, 5.0]);
+        assert_eq!(building.floor_at(0.0), 0);
+        assert_eq!(building.floor_at(3.0), 1);
+        assert_eq!(building.floor_at(7.0), 2);
+        assert_eq!(building.floor_at(12.0), 3);
+        // Closest to floor 1 (at 3.0m) than floor 0 (at 0.0m) or floor 2 (at 7.0m).
+        assert_eq!(building.floor_at(4.0), 1);
+    }
+
+    #[test]
+    fn travel_time_is_zero_for_no_distance() {
+        assert_eq!(travel_time(0.0, MAX_ACCELERATION, MAX_VELOCITY), 0.0);
+    }
+
+    #[test]
+    fn travel_time_never_reaches_cruise_for_a_short_hop() {
+        // accel_distance = v^2 / (2a) = 3.125m, so a 2m hop never hits max velocity.
+        let short = travel_time(2.0, MAX_ACCELERATION, MAX_VELOCITY);
+        let pure_accel = 2.0 * (2.0 / MAX_ACCELERATION).sqrt();
+        assert!((short - pure_accel).abs() < 1e-9);
+    }
+
+    #[test]
+    fn travel_time_includes_a_cruise_leg_for_a_long_hop() {
+        let accel_distance = MAX_VELOCITY * MAX_VELOCITY / (2.0 * MAX_ACCELERATION);
+        let distance = 2.0 * accel_distance + 10.0;
+        let expected = 2.0 * (MAX_VELOCITY / MAX_ACCELERATION) + 10.0 / MAX_VELOCITY;
+        assert!((travel_time(distance, MAX_ACCELERATION, MAX_VELOCITY) - expected).abs() < 1e-9);
+    }
+
+    fn idle_elevator(
+        id: usize,
+    ) -> (
+        ElevatorHandle,
+        Arc<Mutex<ElevatorState>>,
+        mpsc::Receiver<Command>,
+    ) {
+        let (sender, receiver) = mpsc::channel();
+        let state = Arc::new(Mutex::new(ElevatorState::new(id)));
+        (
+            ElevatorHandle {
+                sender,
+                state: Arc::clone(&state),
+            },
+            state,
+            receiver,
+        )
+    }
+
+    #[test]
+    fn dispatch_optimal_assigns_the_nearest_idle_elevator() {
+        let building = Arc::new(Building::new(vec![3.0; 10]));
+
+        let (near, near_state, _near_receiver) = idle_elevator(0);
+        let (far, far_state, _far_receiver) = idle_elevator(1);
+        far_state.lock().unwrap().location = building.cumulative_height(8);
+
+        let controller = Controller::new(vec![near, far], Arc::clone(&building));
+        let mapping = controller.dispatch_optimal(&[(1, Direction::Up)]);
+
+        assert_eq!(mapping, vec![(1, 0)]);
+        let _ = near_state;
+    }
+
+    #[test]
+    fn dispatch_optimal_prefers_a_farther_idle_elevator_over_a_nearer_one_moving_away() {
+        let building = Arc::new(Building::new(vec![3.0; 10]));
+
+        // Nearer in raw distance to floor 1, but already past it while
+        // moving away (Up), so it pays `SAME_DIRECTION_BEHIND_PENALTY_MILLIS`.
+        let (wrong_way, wrong_way_state, _wrong_way_receiver) = idle_elevator(0);
+        {
+            let mut state = wrong_way_state.lock().unwrap();
+            state.location = building.cumulative_height(5);
+            state.direction = Direction::Up;
+        }
+
+        // Farther in raw distance, but idle, so it pays no penalty at all.
+        let (idle_far, idle_far_state, _idle_far_receiver) = idle_elevator(1);
+        idle_far_state.lock().unwrap().location = building.cumulative_height(8);
+
+        let controller = Controller::new(vec![wrong_way, idle_far], Arc::clone(&building));
+        let mapping = controller.dispatch_optimal(&[(1, Direction::Up)]);
+
+        assert_eq!(mapping, vec![(1, 1)]);
+    }
+
+    fn idle_sim_car(location: f64) -> SimCar {
+        SimCar {
+            location,
+            velocity: 0.0,
+            direction: Direction::Idle,
+            stops: BTreeSet::new(),
+        }
+    }
+
+    #[test]
+    fn simulate_tick_arrives_and_clears_the_stop() {
+        let building = Building::new(vec![1.0; 100]);
+        let motion = SmoothMotionController::new(MAX_ACCELERATION, MAX_VELOCITY);
+        let mut car = idle_sim_car(0.0);
+        car.stops.insert(5);
+
+        // travel_time(5m) rounds up to well under 400 ticks (40s); bail out
+        // if it never arrives so a regression shows up as a failure, not a hang.
+        for _ in 0..400 {
+            if car.stops.is_empty() {
+                break;
+            }
+            simulate_tick(&mut car, &building, &motion);
+        }
+
+        assert!(car.stops.is_empty());
+        assert_eq!(car.location, 5.0);
+        assert_eq!(car.velocity, 0.0);
+    }
+
+    #[test]
+    fn rollout_cost_is_lower_when_the_stop_clears_within_the_horizon() {
+        let building = Building::new(vec![1.0; 100]);
+        let motion = SmoothMotionController::new(MAX_ACCELERATION, MAX_VELOCITY);
+        let scheduler = BeamScheduler::new(DEFAULT_BEAM_WIDTH, 40);
+
+        let mut near = idle_sim_car(0.0);
+        near.stops.insert(3);
+        let clears_in_time = BeamPlan {
+            assignment: vec![Some(0)],
+            cars: vec![near, idle_sim_car(53.0)],
+            cost: 0.0,
+        };
+
+        let mut far = idle_sim_car(53.0);
+        far.stops.insert(3);
+        let never_clears = BeamPlan {
+            assignment: vec![Some(1)],
+            cars: vec![idle_sim_car(0.0), far],
+            cost: 0.0,
+        };
+
+        let near_cost = scheduler.rollout_cost(&clears_in_time, &motion, &building);
+        let far_cost = scheduler.rollout_cost(&never_clears, &motion, &building);
+
+        assert!(near_cost < far_cost);
+    }
+
+    #[test]
+    fn plan_prefers_the_elevator_that_clears_its_stop_sooner() {
+        let building = Building::new(vec![1.0; 100]);
+        // 40 ticks (4s) is enough for a 3m hop but not a 50m one, so the two
+        // cars' rollout costs diverge based on whether they finish in time.
+        let scheduler = BeamScheduler::new(DEFAULT_BEAM_WIDTH, 40);
+        let cars = vec![idle_sim_car(0.0), idle_sim_car(53.0)];
+
+        let assigned = scheduler.plan(&cars, &building, &[(3, Direction::Up)]);
+
+        assert_eq!(assigned, Some(0));
+    }
+
+    #[test]
+    fn beam_width_of_one_keeps_only_the_lowest_cost_candidate() {
+        let building = Building::new(vec![1.0; 100]);
+        let scheduler = BeamScheduler::new(1, 40);
+        // Three candidates compete for the single slot beam_width=1 leaves
+        // standing; only the nearest car should survive the truncation.
+        let cars = vec![idle_sim_car(50.0), idle_sim_car(3.0), idle_sim_car(30.0)];
+
+        let assigned = scheduler.plan(&cars, &building, &[(3, Direction::Up)]);
+
+        assert_eq!(assigned, Some(1));
+    }
+
+    /// Regression test for the bug where a car cruising at `max_velocity` in
+    /// one direction, given a target that now lies behind it, would compare
+    /// unsigned `velocity.abs() < max_velocity` (false, since they're equal)
+    /// and coast forever instead of decelerating and reversing.
+    #[test]
+    fn reverses_when_target_falls_behind_a_cruising_car() {
+        let motion = SmoothMotionController::new(MAX_ACCELERATION, MAX_VELOCITY);
+        let accel = motion.next_acceleration(MAX_VELOCITY, -5.0);
+        assert_eq!(accel, -MAX_ACCELERATION);
+    }
+
+    #[test]
+    fn parse_scenario_parses_floors_heights_and_requests() {
+        let input = "floors 3\nheights 4.0 3.0 3.0\n# a comment\nrequest 1 up\nrequest 2 down\n";
+        let (building, requests) = parse_scenario(input).unwrap();
+
+        assert_eq!(building.cumulative_height(3), 10.0);
+        assert_eq!(requests, vec![(1, Direction::Up), (2, Direction::Down)]);
+    }
+
+    #[test]
+    fn parse_scenario_requires_a_heights_line() {
+        let err = parse_scenario("floors 3\n").err().unwrap();
+        assert!(err.contains("missing a `heights` line"));
+    }
+
+    #[test]
+    fn parse_scenario_rejects_a_floors_count_mismatch() {
+        let err = parse_scenario("floors 2\nheights 3.0 3.0 3.0\n")
+            .err()
+            .unwrap();
+        assert!(err.contains("`floors 2` does not match 3 heights"));
+    }
+
+    #[test]
+    fn parse_scenario_rejects_an_unknown_keyword() {
+        let err = parse_scenario("heights 3.0\nlobby 1\n").err().unwrap();
+        assert!(err.contains("unknown keyword `lobby`"));
+    }
+
+    #[test]
+    fn parse_scenario_rejects_an_invalid_request_floor() {
+        let err = parse_scenario("heights 3.0\nrequest ground up\n")
+            .err()
+            .unwrap();
+        assert!(err.contains("invalid floor"));
+    }
+
+    #[test]
+    fn parse_scenario_rejects_an_invalid_request_direction() {
+        let err = parse_scenario("heights 3.0\nrequest 1 sideways\n")
+            .err()
+            .unwrap();
+        assert!(err.contains("invalid direction `sideways`"));
+    }
+
+    #[test]
+    fn run_interactive_call_command_enqueues_and_dispatch_commits_it() {
+        let building = Arc::new(Building::new(vec![3.0; 10]));
+        let (elevator, _state, receiver) = idle_elevator(0);
+        let controller = Controller::new(vec![elevator], Arc::clone(&building));
+        let mut queue = RequestQueue::new();
+
+        run_interactive(
+            &controller,
+            &mut queue,
+            io::Cursor::new(b"call 5 up\ndispatch\n" as &[u8]),
+        );
+
+        assert!(queue.is_empty());
+        assert!(matches!(receiver.try_recv(), Ok(Command::AddStop(5))));
+    }
+
+    #[test]
+    fn run_interactive_goto_command_carries_no_direction_and_drains_at_eof() {
+        let building = Arc::new(Building::new(vec![3.0; 10]));
+        let (elevator, _state, receiver) = idle_elevator(0);
+        let controller = Controller::new(vec![elevator], Arc::clone(&building));
+        let mut queue = RequestQueue::new();
+
+        // No explicit `dispatch`: reaching EOF must still drain the queue.
+        run_interactive(
+            &controller,
+            &mut queue,
+            io::Cursor::new(b"goto 3\n" as &[u8]),
+        );
+
+        assert!(queue.is_empty());
+        assert!(matches!(receiver.try_recv(), Ok(Command::AddStop(3))));
+    }
+
+    #[test]
+    fn run_interactive_rejects_a_malformed_call_without_enqueuing_it() {
+        let building = Arc::new(Building::new(vec![3.0; 10]));
+        let (elevator, _state, receiver) = idle_elevator(0);
+        let controller = Controller::new(vec![elevator], Arc::clone(&building));
+        let mut queue = RequestQueue::new();
+
+        run_interactive(
+            &controller,
+            &mut queue,
+            io::Cursor::new(b"call 5\ncall sideways up\n" as &[u8]),
+        );
+
+        assert!(queue.is_empty());
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn run_interactive_status_command_polls_every_elevator() {
+        let building = Arc::new(Building::new(vec![3.0; 10]));
+        let (first, _first_state, first_receiver) = idle_elevator(0);
+        let (second, _second_state, second_receiver) = idle_elevator(1);
+        let controller = Controller::new(vec![first, second], Arc::clone(&building));
+        let mut queue = RequestQueue::new();
+
+        run_interactive(
+            &controller,
+            &mut queue,
+            io::Cursor::new(b"status\n" as &[u8]),
+        );
+
+        assert!(matches!(first_receiver.try_recv(), Ok(Command::Status)));
+        assert!(matches!(second_receiver.try_recv(), Ok(Command::Status)));
+    }
+
+    #[test]
+    fn run_interactive_ignores_unknown_commands() {
+        let building = Arc::new(Building::new(vec![3.0; 10]));
+        let (elevator, _state, receiver) = idle_elevator(0);
+        let controller = Controller::new(vec![elevator], Arc::clone(&building));
+        let mut queue = RequestQueue::new();
+
+        run_interactive(
+            &controller,
+            &mut queue,
+            io::Cursor::new(b"frobnicate\n" as &[u8]),
+        );
+
+        assert!(queue.is_empty());
+        assert!(receiver.try_recv().is_err());
     }
 }